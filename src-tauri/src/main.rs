@@ -4,6 +4,7 @@
 
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -24,6 +25,52 @@ struct AppState {
     api_port: Mutex<Option<u16>>,
     launcher_config: Mutex<Option<LauncherConfig>>,
     child_pid: Mutex<Option<u32>>,
+    log_buffer: Mutex<std::collections::VecDeque<LogEntry>>,
+    log_seq: Mutex<u64>,
+    status: Mutex<BackendStatus>,
+    /// 每次“有意”的启动/停止/重启都会递增，供 supervisor 判断自己监控的子进程是否已被取代
+    generation: Mutex<u64>,
+    /// update_config 自己刚写入的 launcher.json 内容，供文件 watcher 识别并忽略这次自触发的变更
+    last_applied_config: Mutex<Option<String>>,
+}
+
+/// 递增并返回全局 generation，调用方用于使任何仍在退避等待的旧 supervisor 失效
+fn bump_generation(state: &AppState) -> u64 {
+    let mut generation = state.generation.lock().unwrap();
+    *generation += 1;
+    *generation
+}
+
+/// 后端生命周期状态，供前端驱动启动/停止/重启工具栏
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum BackendStatus {
+    Starting,
+    Ready,
+    Stopped,
+    Crashed,
+}
+
+/// 后端日志环形缓冲中的一条结构化记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LogEntry {
+    seq: u64,
+    timestamp: u64,
+    level: String,
+    message: String,
+}
+
+/// 日志环形缓冲最多保留的条数
+const MAX_LOG_ENTRIES: usize = 2000;
+
+/// 检测到的打包格式，供前端在沙箱环境下提示用户
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum PackagingFormat {
+    AppImage,
+    Flatpak,
+    Snap,
+    Native,
 }
 
 /// 获取 launcher.json 的路径
@@ -44,11 +91,12 @@ fn read_launcher_config() -> Option<LauncherConfig> {
     serde_json::from_str(&content).ok()
 }
 
-fn write_launcher_config(config: &LauncherConfig) -> Result<(), String> {
+/// 写入 launcher.json，返回写入的 JSON 文本供调用方识别自己触发的文件变更事件
+fn write_launcher_config(config: &LauncherConfig) -> Result<String, String> {
     let path = launcher_config_path();
     let json = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
-    fs::write(&path, json).map_err(|e| e.to_string())?;
-    Ok(())
+    fs::write(&path, &json).map_err(|e| e.to_string())?;
+    Ok(json)
 }
 
 /// 定位 sidecar 二进制：与主程序在同一目录 (Contents/MacOS/)
@@ -78,6 +126,139 @@ fn resolve_sidecar_path() -> Result<PathBuf, String> {
     ))
 }
 
+/// 根据常见的沙箱环境变量判断当前打包格式
+#[cfg(target_os = "linux")]
+fn detect_packaging_format() -> PackagingFormat {
+    if std::env::var_os("APPIMAGE").is_some() || std::env::var_os("APPDIR").is_some() {
+        PackagingFormat::AppImage
+    } else if std::env::var_os("FLATPAK_ID").is_some() || PathBuf::from("/.flatpak-info").exists()
+    {
+        PackagingFormat::Flatpak
+    } else if std::env::var_os("SNAP").is_some() {
+        PackagingFormat::Snap
+    } else {
+        PackagingFormat::Native
+    }
+}
+
+/// 某些沙箱运行时会把未被污染的原始值备份在 `<VAR>_ORIG` 或 `WEBKIT_<VAR>` 下，优先用它们
+#[cfg(target_os = "linux")]
+fn original_env_value(var: &str) -> Option<String> {
+    std::env::var(format!("{}_ORIG", var))
+        .ok()
+        .or_else(|| std::env::var(format!("WEBKIT_{}", var)).ok())
+}
+
+/// 清理一个冒号分隔的路径列表：去掉指向沙箱内部的条目、保留首次出现的顺序去重、丢弃空条目
+#[cfg(target_os = "linux")]
+fn clean_path_list(value: &str, bundle_prefix: Option<&str>) -> String {
+    let mut seen = std::collections::HashSet::new();
+    value
+        .split(':')
+        .filter(|entry| !entry.is_empty())
+        .filter(|entry| bundle_prefix.map_or(true, |prefix| !entry.starts_with(prefix)))
+        .filter(|entry| seen.insert(*entry))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+#[cfg(target_os = "linux")]
+const PATH_LIST_ENV_VARS: &[&str] = &[
+    "PATH",
+    "LD_LIBRARY_PATH",
+    "XDG_DATA_DIRS",
+    "GST_PLUGIN_PATH",
+    "GST_PLUGIN_SYSTEM_PATH",
+];
+
+/// 修正 sidecar 继承到的 PATH/XDG/动态库相关环境变量，避免 AppImage/Flatpak/Snap 沙箱污染 Python 后端
+#[cfg(target_os = "linux")]
+fn normalize_backend_env(cmd: &mut Command) -> PackagingFormat {
+    let format = detect_packaging_format();
+
+    let bundle_prefix = match format {
+        PackagingFormat::AppImage => std::env::var("APPDIR").ok(),
+        // Flatpak 沙箱里应用根固定挂载在 /app 下
+        PackagingFormat::Flatpak => Some("/app".to_string()),
+        PackagingFormat::Snap => std::env::var("SNAP").ok(),
+        PackagingFormat::Native => None,
+    };
+
+    for var in PATH_LIST_ENV_VARS {
+        let raw = original_env_value(var).unwrap_or_else(|| std::env::var(var).unwrap_or_default());
+        let cleaned = clean_path_list(&raw, bundle_prefix.as_deref());
+        if cleaned.is_empty() {
+            cmd.env_remove(var);
+        } else {
+            cmd.env(var, cleaned);
+        }
+    }
+
+    format
+}
+
+#[tauri::command]
+fn get_runtime_environment() -> PackagingFormat {
+    #[cfg(target_os = "linux")]
+    {
+        detect_packaging_format()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        PackagingFormat::Native
+    }
+}
+
+/// 尝试把一行后端输出解析为结构化日志，追加到环形缓冲并广播给前端。
+/// 能解析出 `level`/`message` 字段的 JSON 行按其分类，否则整行按 `is_stdout` 归为 info/stderr。
+fn record_log(app: &AppHandle, raw_line: &str, is_stdout: bool) {
+    let trimmed = raw_line.trim();
+    if trimmed.is_empty() {
+        return;
+    }
+
+    let parsed = serde_json::from_str::<serde_json::Value>(trimmed).ok().and_then(|value| {
+        let level = value.get("level").and_then(|v| v.as_str())?.to_string();
+        let message = value.get("message").and_then(|v| v.as_str())?.to_string();
+        Some((level, message))
+    });
+
+    let (level, message) = parsed.unwrap_or_else(|| {
+        let level = if is_stdout { "info" } else { "stderr" };
+        (level.to_string(), trimmed.to_string())
+    });
+
+    let state = app.state::<AppState>();
+    let seq = {
+        let mut seq_guard = state.log_seq.lock().unwrap();
+        *seq_guard += 1;
+        *seq_guard
+    };
+    let entry = LogEntry {
+        seq,
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        level,
+        message,
+    };
+
+    {
+        let mut buffer = state.log_buffer.lock().unwrap();
+        if buffer.len() >= MAX_LOG_ENTRIES {
+            buffer.pop_front();
+        }
+        buffer.push_back(entry.clone());
+    }
+    app.emit("backend-log", entry).ok();
+}
+
+#[tauri::command]
+fn get_backend_logs(state: State<AppState>) -> Vec<LogEntry> {
+    state.log_buffer.lock().unwrap().iter().cloned().collect()
+}
+
 #[tauri::command]
 fn get_api_port(state: State<AppState>) -> Option<u16> {
     *state.api_port.lock().unwrap()
@@ -104,50 +285,229 @@ async fn save_config_and_start(
         data_dir: data_dir.clone(),
         env_file: env_file.clone(),
     };
-    write_launcher_config(&config)?;
+    let written_json = write_launcher_config(&config)?;
     *state.launcher_config.lock().unwrap() = Some(config);
+    // 标记这次自己写入的内容，文件 watcher 看到同样的内容时会跳过，避免首次启动时被重复重启一次
+    *state.last_applied_config.lock().unwrap() = Some(written_json);
 
     start_backend(&app, &data_dir, &env_file).await
 }
 
 #[tauri::command]
-fn update_config(
-    state: State<AppState>,
+async fn update_config(
+    app: AppHandle,
+    state: State<'_, AppState>,
     data_dir: String,
     env_file: String,
-) -> Result<(), String> {
+    reload: Option<bool>,
+) -> Result<Option<u16>, String> {
     let config = LauncherConfig {
         data_dir,
         env_file,
     };
-    write_launcher_config(&config)?;
+    let written_json = write_launcher_config(&config)?;
     *state.launcher_config.lock().unwrap() = Some(config);
+
+    if reload.unwrap_or(false) {
+        // 标记这次自己写入的内容，文件 watcher 看到同样的内容时会跳过，避免重复重启
+        *state.last_applied_config.lock().unwrap() = Some(written_json);
+        let port = reload_backend(&app).await?;
+        Ok(Some(port))
+    } else {
+        Ok(None)
+    }
+}
+
+/// 终止已跟踪的子进程（尽力而为，跨平台）
+async fn stop_pid(pid: u32) {
+    #[cfg(unix)]
+    {
+        Command::new("kill").arg(pid.to_string()).status().await.ok();
+    }
+    #[cfg(windows)]
+    {
+        Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/F"])
+            .status()
+            .await
+            .ok();
+    }
+}
+
+/// 停止当前后端、重新读取配置并重启，用于热重载场景
+async fn reload_backend(app: &AppHandle) -> Result<u16, String> {
+    let state = app.state::<AppState>();
+
+    if let Some(pid) = state.child_pid.lock().unwrap().take() {
+        stop_pid(pid).await;
+    }
+    *state.api_port.lock().unwrap() = None;
+
+    let config = read_launcher_config().ok_or("launcher.json missing during reload")?;
+    *state.launcher_config.lock().unwrap() = Some(config.clone());
+
+    let port = start_backend(app, &config.data_dir, &config.env_file).await?;
+    app.emit("backend-reloaded", port).ok();
+    Ok(port)
+}
+
+#[tauri::command]
+async fn stop_backend(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    // 先推进 generation，即使当前没有子进程可杀，也能让正在退避等待的 supervisor 放弃重启
+    bump_generation(&state);
+
+    if let Some(pid) = state.child_pid.lock().unwrap().take() {
+        stop_pid(pid).await;
+    }
+    *state.api_port.lock().unwrap() = None;
+    *state.status.lock().unwrap() = BackendStatus::Stopped;
+    app.emit("backend-stopped", ()).ok();
     Ok(())
 }
 
-/// 启动 Python 后端进程，解析 stdout 获取端口
-async fn start_backend(
-    app: &AppHandle,
+#[tauri::command]
+async fn restart_backend(app: AppHandle, state: State<'_, AppState>) -> Result<u16, String> {
+    // 同上：先让任何旧 supervisor 的待定重启失效，再杀掉旧进程并拉起新的
+    bump_generation(&state);
+
+    if let Some(pid) = state.child_pid.lock().unwrap().take() {
+        stop_pid(pid).await;
+    }
+    *state.api_port.lock().unwrap() = None;
+
+    let config = state
+        .launcher_config
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or("No launcher config to restart from")?;
+
+    start_backend(&app, &config.data_dir, &config.env_file).await
+}
+
+#[tauri::command]
+fn get_backend_status(state: State<AppState>) -> BackendStatus {
+    *state.status.lock().unwrap()
+}
+
+/// 监听 launcher.json 与其指向的 env_file，发生变化时触发热重载
+fn watch_config_changes(app: AppHandle) {
+    std::thread::spawn(move || {
+        let (tx, rx) = std::sync::mpsc::channel::<Event>();
+
+        let mut watcher: RecommendedWatcher = match notify::recommended_watcher(move |res| {
+            if let Ok(event) = res {
+                tx.send(event).ok();
+            }
+        }) {
+            Ok(w) => w,
+            Err(e) => {
+                eprintln!("[tauri] Failed to create config watcher: {}", e);
+                return;
+            }
+        };
+
+        let config_path = launcher_config_path();
+        if let Err(e) = watcher.watch(&config_path, RecursiveMode::NonRecursive) {
+            eprintln!("[tauri] Failed to watch {:?}: {}", config_path, e);
+        }
+
+        // env_file 路径会随配置变化，动态跟随订阅
+        let mut watched_env_file: Option<PathBuf> = read_launcher_config()
+            .map(|c| PathBuf::from(c.env_file))
+            .filter(|p| p.exists());
+        if let Some(env_path) = &watched_env_file {
+            watcher.watch(env_path, RecursiveMode::NonRecursive).ok();
+        }
+
+        for event in rx {
+            if !matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+                continue;
+            }
+
+            // update_config(reload: true) 已经同步触发过一次 reload_backend 了；
+            // 如果这次变更正是它自己刚写入的内容，跳过，避免重复重启一次后端
+            let is_self_triggered = event.paths.iter().any(|p| p == &config_path)
+                && fs::read_to_string(&config_path).ok().is_some_and(|raw| {
+                    let state = app.state::<AppState>();
+                    let mut marker = state.last_applied_config.lock().unwrap();
+                    if marker.as_deref() == Some(raw.as_str()) {
+                        *marker = None;
+                        true
+                    } else {
+                        false
+                    }
+                });
+
+            if let Some(config) = read_launcher_config() {
+                let env_path = PathBuf::from(&config.env_file);
+                if watched_env_file.as_ref() != Some(&env_path) {
+                    if let Some(old) = watched_env_file.take() {
+                        watcher.unwatch(&old).ok();
+                    }
+                    if env_path.exists() && watcher.watch(&env_path, RecursiveMode::NonRecursive).is_ok() {
+                        watched_env_file = Some(env_path);
+                    }
+                }
+            }
+
+            if is_self_triggered {
+                continue;
+            }
+
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = reload_backend(&app).await {
+                    eprintln!("[tauri] Hot-reload failed: {}", e);
+                    app.emit("backend-error", e).ok();
+                }
+            });
+        }
+    });
+}
+
+type BackendStdoutLines = tokio::io::Lines<BufReader<tokio::process::ChildStdout>>;
+type BackendStderrLines = tokio::io::Lines<BufReader<tokio::process::ChildStderr>>;
+
+const INITIAL_BACKOFF_SECS: u64 = 1;
+const MAX_BACKOFF_SECS: u64 = 30;
+const MAX_BACKEND_RETRIES: u32 = 8;
+const HEALTHY_UPTIME_SECS: u64 = 60;
+const DEFAULT_STARTUP_TIMEOUT_SECS: u64 = 30;
+
+/// 端口握手超时时长，可通过 `PAPERMIND_STARTUP_TIMEOUT_SECS` 覆盖默认值
+fn startup_timeout_secs() -> u64 {
+    std::env::var("PAPERMIND_STARTUP_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_STARTUP_TIMEOUT_SECS)
+}
+
+/// 拉起子进程并等待首行 JSON 端口握手，返回句柄、PID 与已分行的输出流
+async fn spawn_backend_process(
     data_dir: &str,
     env_file: &str,
-) -> Result<u16, String> {
+) -> Result<(tokio::process::Child, u32, u16, BackendStdoutLines, BackendStderrLines), String> {
     let sidecar_path = resolve_sidecar_path()?;
     eprintln!("[tauri] Starting backend: {:?}", sidecar_path);
 
-    let mut child = Command::new(&sidecar_path)
-        .env("PAPERMIND_DATA_DIR", data_dir)
+    let mut cmd = Command::new(&sidecar_path);
+    cmd.env("PAPERMIND_DATA_DIR", data_dir)
         .env("PAPERMIND_ENV_FILE", env_file)
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
-        .kill_on_drop(true)
+        .kill_on_drop(true);
+
+    #[cfg(target_os = "linux")]
+    {
+        normalize_backend_env(&mut cmd);
+    }
+
+    let mut child = cmd
         .spawn()
         .map_err(|e| format!("Failed to spawn backend: {}", e))?;
 
     let pid = child.id().unwrap_or(0);
-    {
-        let state = app.state::<AppState>();
-        *state.child_pid.lock().unwrap() = Some(pid);
-    }
     eprintln!("[tauri] Backend PID: {}", pid);
 
     // 读 stdout 获取端口 JSON
@@ -163,59 +523,139 @@ async fn start_backend(
     let mut stdout_reader = BufReader::new(stdout).lines();
     let mut stderr_reader = BufReader::new(stderr).lines();
 
-    // 等待首行 JSON 端口信息
-    let port: u16;
-    loop {
-        tokio::select! {
-            line = stdout_reader.next_line() => {
-                match line {
-                    Ok(Some(text)) => {
-                        let trimmed = text.trim();
-                        if let Ok(info) = serde_json::from_str::<serde_json::Value>(trimmed) {
-                            if let Some(p) = info.get("port").and_then(|v| v.as_u64()) {
-                                port = p as u16;
-                                break;
+    // 等待首行 JSON 端口信息，超时则视为启动失败并杀掉僵死进程
+    let handshake = async {
+        loop {
+            tokio::select! {
+                line = stdout_reader.next_line() => {
+                    match line {
+                        Ok(Some(text)) => {
+                            let trimmed = text.trim();
+                            if let Ok(info) = serde_json::from_str::<serde_json::Value>(trimmed) {
+                                if let Some(p) = info.get("port").and_then(|v| v.as_u64()) {
+                                    return Ok(p as u16);
+                                }
                             }
+                            eprintln!("[backend stdout] {}", trimmed);
+                        }
+                        Ok(None) => {
+                            return Err("Backend process exited before sending port".to_string());
+                        }
+                        Err(e) => {
+                            return Err(format!("Error reading backend stdout: {}", e));
                         }
-                        eprintln!("[backend stdout] {}", trimmed);
-                    }
-                    Ok(None) => {
-                        return Err("Backend process exited before sending port".to_string());
-                    }
-                    Err(e) => {
-                        return Err(format!("Error reading backend stdout: {}", e));
                     }
                 }
-            }
-            line = stderr_reader.next_line() => {
-                if let Ok(Some(text)) = line {
-                    eprintln!("[backend stderr] {}", text.trim());
+                line = stderr_reader.next_line() => {
+                    if let Ok(Some(text)) = line {
+                        eprintln!("[backend stderr] {}", text.trim());
+                    }
                 }
             }
         }
-    }
+    };
+
+    let port = match tokio::time::timeout(
+        std::time::Duration::from_secs(startup_timeout_secs()),
+        handshake,
+    )
+    .await
+    {
+        Ok(Ok(port)) => port,
+        Ok(Err(e)) => {
+            child.start_kill().ok();
+            return Err(e);
+        }
+        Err(_) => {
+            child.start_kill().ok();
+            return Err(format!(
+                "Backend did not report a port within {}s",
+                startup_timeout_secs()
+            ));
+        }
+    };
+
+    Ok((child, pid, port, stdout_reader, stderr_reader))
+}
+
+/// 启动 Python 后端进程并交给 supervisor 持续监控
+async fn start_backend(
+    app: &AppHandle,
+    data_dir: &str,
+    env_file: &str,
+) -> Result<u16, String> {
+    let my_generation = {
+        let state = app.state::<AppState>();
+        *state.status.lock().unwrap() = BackendStatus::Starting;
+        bump_generation(&state)
+    };
+
+    let (child, pid, port, stdout_reader, stderr_reader) =
+        match spawn_backend_process(data_dir, env_file).await {
+            Ok(v) => v,
+            Err(e) => {
+                let state = app.state::<AppState>();
+                *state.status.lock().unwrap() = BackendStatus::Crashed;
+                return Err(e);
+            }
+        };
 
-    // 存储端口并通知前端
     {
         let state = app.state::<AppState>();
+        *state.child_pid.lock().unwrap() = Some(pid);
         *state.api_port.lock().unwrap() = Some(port);
+        *state.status.lock().unwrap() = BackendStatus::Ready;
     }
     app.emit("backend-ready", port).ok();
     eprintln!("[tauri] Backend ready on port {}", port);
 
-    // 后台转发日志 + 监控进程退出
-    tauri::async_runtime::spawn(async move {
+    tauri::async_runtime::spawn(supervise_backend(
+        app.clone(),
+        child,
+        stdout_reader,
+        stderr_reader,
+        data_dir.to_string(),
+        env_file.to_string(),
+        my_generation,
+    ));
+
+    Ok(port)
+}
+
+/// 转发日志并在子进程退出后按指数退避自动重启；长期健康运行会重置退避计数。
+/// 如果子进程是被 reload/stop 主动终止的（`child_pid` 已被替换），则不视为崩溃。
+async fn supervise_backend(
+    app: AppHandle,
+    mut child: tokio::process::Child,
+    mut stdout_reader: BackendStdoutLines,
+    mut stderr_reader: BackendStderrLines,
+    data_dir: String,
+    env_file: String,
+    generation: u64,
+) {
+    let mut attempt: u32 = 0;
+
+    loop {
+        let my_pid = child.id().unwrap_or(0);
+        let started_at = std::time::Instant::now();
+
         loop {
             tokio::select! {
                 line = stdout_reader.next_line() => {
                     match line {
-                        Ok(Some(text)) => eprintln!("[backend] {}", text.trim()),
+                        Ok(Some(text)) => {
+                            eprintln!("[backend] {}", text.trim());
+                            record_log(&app, &text, true);
+                        }
                         _ => break,
                     }
                 }
                 line = stderr_reader.next_line() => {
                     match line {
-                        Ok(Some(text)) => eprintln!("[backend] {}", text.trim()),
+                        Ok(Some(text)) => {
+                            eprintln!("[backend] {}", text.trim());
+                            record_log(&app, &text, false);
+                        }
                         _ => break,
                     }
                 }
@@ -228,13 +668,115 @@ async fn start_backend(
                 }
             }
         }
-    });
 
-    Ok(port)
+        let owns_current_child = {
+            let state = app.state::<AppState>();
+            *state.child_pid.lock().unwrap() == Some(my_pid)
+        };
+        if !owns_current_child {
+            // 进程是被 reload_backend/stop_backend/restart_backend 主动终止的，是否重新拉起由调用方决定
+            return;
+        }
+
+        {
+            let state = app.state::<AppState>();
+            *state.api_port.lock().unwrap() = None;
+            *state.child_pid.lock().unwrap() = None;
+            *state.status.lock().unwrap() = BackendStatus::Crashed;
+        }
+        app.emit("backend-crashed", ()).ok();
+
+        if started_at.elapsed() >= std::time::Duration::from_secs(HEALTHY_UPTIME_SECS) {
+            attempt = 0;
+        } else {
+            attempt += 1;
+        }
+
+        if attempt > MAX_BACKEND_RETRIES {
+            eprintln!(
+                "[tauri] Backend crash-looped past {} retries, giving up",
+                MAX_BACKEND_RETRIES
+            );
+            app.emit("backend-failed", ()).ok();
+            return;
+        }
+
+        let exponent = attempt.saturating_sub(1).min(5);
+        let backoff = (INITIAL_BACKOFF_SECS << exponent).min(MAX_BACKOFF_SECS);
+        eprintln!(
+            "[tauri] Restarting backend in {}s (attempt {})",
+            backoff, attempt
+        );
+        tokio::time::sleep(std::time::Duration::from_secs(backoff)).await;
+
+        // 退避期间 stop_backend/restart_backend 可能已经介入并推进了 generation，
+        // 此时这次自动重启已经过期，必须放弃，否则会和它们各自启动的子进程打架
+        if *app.state::<AppState>().generation.lock().unwrap() != generation {
+            eprintln!("[tauri] Abandoning scheduled backend restart (generation advanced)");
+            return;
+        }
+
+        {
+            let state = app.state::<AppState>();
+            *state.status.lock().unwrap() = BackendStatus::Starting;
+        }
+
+        match spawn_backend_process(&data_dir, &env_file).await {
+            Ok((mut new_child, pid, port, new_stdout, new_stderr)) => {
+                // 握手耗时也可能跨越 stop/restart 的介入窗口，提交状态前再确认一次
+                if *app.state::<AppState>().generation.lock().unwrap() != generation {
+                    eprintln!(
+                        "[tauri] Discarding stale backend restart (generation advanced during handshake)"
+                    );
+                    new_child.start_kill().ok();
+                    return;
+                }
+
+                {
+                    let state = app.state::<AppState>();
+                    *state.child_pid.lock().unwrap() = Some(pid);
+                    *state.api_port.lock().unwrap() = Some(port);
+                    *state.status.lock().unwrap() = BackendStatus::Ready;
+                }
+                app.emit("backend-ready", port).ok();
+                eprintln!("[tauri] Backend restarted on port {}", port);
+
+                child = new_child;
+                stdout_reader = new_stdout;
+                stderr_reader = new_stderr;
+            }
+            Err(e) => {
+                eprintln!("[tauri] Failed to restart backend: {}", e);
+                {
+                    let state = app.state::<AppState>();
+                    *state.status.lock().unwrap() = BackendStatus::Crashed;
+                }
+                app.emit("backend-error", e).ok();
+                return;
+            }
+        }
+    }
 }
 
 fn main() {
     tauri::Builder::default()
+        // 单实例必须最先注册：第二次启动会在这里被拦截，不再执行 setup/start_backend
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            eprintln!(
+                "[tauri] Second instance launched (args={:?}, cwd={}), focusing existing window",
+                args, cwd
+            );
+            if let Some(window) = app.get_webview_window("main") {
+                window.unminimize().ok();
+                window.set_focus().ok();
+            }
+            let port = *app.state::<AppState>().api_port.lock().unwrap();
+            app.emit(
+                "single-instance",
+                serde_json::json!({ "args": args, "cwd": cwd, "port": port }),
+            )
+            .ok();
+        }))
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
@@ -242,6 +784,11 @@ fn main() {
             api_port: Mutex::new(None),
             launcher_config: Mutex::new(read_launcher_config()),
             child_pid: Mutex::new(None),
+            log_buffer: Mutex::new(std::collections::VecDeque::with_capacity(MAX_LOG_ENTRIES)),
+            log_seq: Mutex::new(0),
+            status: Mutex::new(BackendStatus::Stopped),
+            generation: Mutex::new(0),
+            last_applied_config: Mutex::new(None),
         })
         .invoke_handler(tauri::generate_handler![
             get_api_port,
@@ -249,6 +796,11 @@ fn main() {
             get_launcher_config,
             save_config_and_start,
             update_config,
+            get_runtime_environment,
+            get_backend_logs,
+            stop_backend,
+            restart_backend,
+            get_backend_status,
         ])
         .setup(|app| {
             let handle = app.handle().clone();
@@ -270,6 +822,8 @@ fn main() {
                 });
             }
 
+            watch_config_changes(app.handle().clone());
+
             Ok(())
         })
         .run(tauri::generate_context!())